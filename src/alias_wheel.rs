@@ -0,0 +1,71 @@
+extern crate rand;
+extern crate num;
+
+use std::fmt::Display;
+use self::rand::Rng;
+use self::rand::distributions::range::SampleRange;
+use self::num::Float;
+
+/// A frozen, O(1)-per-draw sampler built from a [`RandomWheel`](::RandomWheel)
+/// with [`build_alias`](::RandomWheel::build_alias), using
+/// [Walker's alias method](https://en.wikipedia.org/wiki/Alias_method).
+///
+/// Unlike `RandomWheel::peek`, this table is immutable: it must be rebuilt
+/// whenever the originating wheel's items or probabilities change.
+pub struct AliasTable<P: SampleRange + Float + Display, T, R: Rng> {
+    /// the original (probability, data) pairs, indexed identically to `prob`/`alias`.
+    items: Vec<(P, T)>,
+    /// per-slot acceptance probability of the alias method.
+    prob: Vec<P>,
+    /// per-slot alias to fall back on when the coin flip misses.
+    alias: Vec<usize>,
+    /// the generator used to draw from this table.
+    rng: R
+}
+
+impl<P: SampleRange + Float + Display, T, R: Rng> AliasTable<P, T, R> {
+    /// builds an `AliasTable` from its raw parts. Used by `RandomWheel::build_alias`.
+    pub(crate) fn from_parts(items: Vec<(P, T)>, prob: Vec<P>, alias: Vec<usize>, rng: R) -> AliasTable<P, T, R> {
+        AliasTable { items, prob, alias, rng }
+    }
+
+    /// returns the number of elements in the table.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// returns `true` if this table is empty else return `false`.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// draws a weighted-random element in O(1), without removing it.
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::RandomWheel;
+    /// extern crate rand;
+    ///
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
+    ///
+    /// rw.push(5., 'a');
+    /// rw.push(1., 'b');
+    ///
+    /// let mut table = rw.build_alias(rand::thread_rng());
+    /// assert!(table.sample().is_some());
+    /// ```
+    pub fn sample(&mut self) -> Option<(P, &T)> {
+
+        let n = self.items.len();
+        if n == 0 {
+            return None;
+        }
+
+        let i = self.rng.gen_range(0, n);
+        let coin: P = self.rng.gen_range(P::zero(), P::one());
+
+        let id = if coin < self.prob[i] { i } else { self.alias[i] };
+
+        self.items.get(id).map(|&(proba, ref data)| (proba, data))
+    }
+}
@@ -0,0 +1,84 @@
+extern crate num;
+
+use self::num::Float;
+
+/// a minimal binary-indexed (Fenwick) tree over per-slot `Float` weights,
+/// answering prefix-sum and prefix-sum-search queries in O(log n).
+///
+/// Used internally by [`IndexedRandomWheel`](::IndexedRandomWheel) to keep
+/// weighted draws and weight edits logarithmic instead of the linear scan
+/// `RandomWheel` does.
+pub(crate) struct FenwickTree<P: Float> {
+    // 1-indexed internally; tree[0] is unused padding.
+    tree: Vec<P>
+}
+
+impl<P: Float> FenwickTree<P> {
+    /// builds a tree with room for `capacity` slots, all weights at zero.
+    pub fn with_capacity(capacity: usize) -> FenwickTree<P> {
+        FenwickTree { tree: vec![P::zero(); capacity + 1] }
+    }
+
+    /// number of slots this tree can hold.
+    pub fn capacity(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    /// adds `delta` to the weight at slot `index` (0-indexed).
+    pub fn add(&mut self, index: usize, delta: P) {
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] = self.tree[i] + delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// sum of weights in `[0, index]` (0-indexed, inclusive).
+    pub fn prefix_sum(&self, index: usize) -> P {
+        let mut i = index + 1;
+        let mut sum = P::zero();
+        while i > 0 {
+            sum = sum + self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+
+    /// sum of all weights currently held.
+    pub fn total(&self) -> P {
+        let n = self.capacity();
+        if n == 0 { P::zero() } else { self.prefix_sum(n - 1) }
+    }
+
+    /// the weight currently stored at a single slot.
+    pub fn get(&self, index: usize) -> P {
+        let upper = self.prefix_sum(index);
+        let lower = if index == 0 { P::zero() } else { self.prefix_sum(index - 1) };
+        upper - lower
+    }
+
+    /// smallest index whose prefix sum strictly exceeds `target`, or `None`
+    /// if `target` is at or beyond the total weight.
+    pub fn find(&self, target: P) -> Option<usize> {
+
+        let n = self.capacity();
+        if n == 0 {
+            return None;
+        }
+
+        let mut pos = 0usize;
+        let mut remaining = target;
+        let mut step = n.next_power_of_two();
+
+        while step > 0 {
+            let next = pos + step;
+            if next <= n && self.tree[next] <= remaining {
+                pos = next;
+                remaining = remaining - self.tree[next];
+            }
+            step >>= 1;
+        }
+
+        if pos < n { Some(pos) } else { None }
+    }
+}
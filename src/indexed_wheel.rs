@@ -0,0 +1,156 @@
+extern crate rand;
+extern crate num;
+
+use std::fmt::Display;
+use self::rand::Rng;
+use self::rand::distributions::range::SampleRange;
+use self::num::Float;
+use ::fenwick::FenwickTree;
+
+/// An opt-in, Fenwick-tree-backed cousin of [`RandomWheel`](::RandomWheel) for
+/// wheels that are both large and popped often: `push`, `pop`, and weight
+/// edits all run in O(log n) instead of the O(n) scan `RandomWheel` does.
+///
+/// A popped slot is tombstoned (its weight zeroed) rather than shifted, which
+/// is what keeps `pop` logarithmic; `len()` still reports only live elements.
+/// Tombstoned slots are recycled by `push` via a free-list, so interleaving
+/// `push`/`pop` across many generations (tournament selection, GA fitness
+/// re-evaluation) doesn't grow `items`/the tree without bound.
+pub struct IndexedRandomWheel<P: SampleRange + Float + Display, T, R: Rng> {
+    weights: FenwickTree<P>,
+    items: Vec<Option<T>>,
+    /// tombstoned slot indices available for `push` to reuse.
+    free: Vec<usize>,
+    len: usize,
+    rng: R
+}
+
+impl<P: SampleRange + Float + Display, T, R: Rng> IndexedRandomWheel<P, T, R> {
+    /// creates a new empty indexed wheel with room for `capacity` elements
+    /// before its tree needs to be rebuilt at a larger size.
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::IndexedRandomWheel;
+    /// extern crate rand;
+    ///
+    /// let rw: IndexedRandomWheel<f64, char, _> = IndexedRandomWheel::with_capacity(16, rand::thread_rng());
+    ///
+    /// assert_eq!(rw.len(), 0);
+    /// ```
+    pub fn with_capacity(capacity: usize, rng: R) -> IndexedRandomWheel<P, T, R> {
+        IndexedRandomWheel {
+            weights: FenwickTree::with_capacity(capacity),
+            items: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            len: 0,
+            rng
+        }
+    }
+
+    /// returns the number of live elements in the wheel.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// returns `true` if this wheel holds no live elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// returns the number of slots currently allocated: live, tombstoned, or
+    /// not yet used.
+    /// `push` only grows this when the free-list from prior `pop`s is empty.
+    pub fn capacity(&self) -> usize {
+        self.weights.capacity()
+    }
+
+    /// returns total of luck you pushed, minus whatever has been popped.
+    pub fn proba_sum(&self) -> P {
+        self.weights.total()
+    }
+
+    /// add an element associated with a probability, in amortized O(log n).
+    /// Reuses a tombstoned slot left by `pop` when one is available, rather
+    /// than growing the wheel.
+    pub fn push(&mut self, proba: P, data: T) {
+
+        assert!(proba > P::zero(), "proba {} is lower or equal to zero!", proba);
+
+        if let Some(index) = self.free.pop() {
+            self.items[index] = Some(data);
+            self.weights.add(index, proba);
+            self.len += 1;
+            return;
+        }
+
+        if self.items.len() == self.weights.capacity() {
+            let new_capacity = if self.weights.capacity() == 0 { 1 } else { self.weights.capacity() * 2 };
+            self.grow(new_capacity);
+        }
+
+        let index = self.items.len();
+        self.items.push(Some(data));
+        self.weights.add(index, proba);
+        self.len += 1;
+    }
+
+    /// rebuilds the tree at a larger capacity, preserving every slot's weight.
+    fn grow(&mut self, new_capacity: usize) {
+
+        let mut grown = FenwickTree::with_capacity(new_capacity);
+        for index in 0..self.items.len() {
+            let weight = self.weights.get(index);
+            if weight > P::zero() {
+                grown.add(index, weight);
+            }
+        }
+        self.weights = grown;
+    }
+
+    /// updates the weight of the (still live) element at `index`, in O(log n).
+    pub fn set_weight(&mut self, index: usize, proba: P) {
+        let current = self.weights.get(index);
+        self.weights.add(index, proba - current);
+    }
+
+    /// returns a random live slot index in O(log n), or `None` if the wheel
+    /// has no weight left to draw from.
+    fn get_random_index(&mut self) -> Option<usize> {
+
+        let sum = self.weights.total();
+        if sum <= P::zero() {
+            return None;
+        }
+
+        let dist = self.rng.gen_range(P::zero(), sum);
+        self.weights.find(dist)
+    }
+
+    /// removes a randomly drawn element and returns it with its probability,
+    /// in O(log n).
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::IndexedRandomWheel;
+    /// extern crate rand;
+    ///
+    /// let mut rw = IndexedRandomWheel::with_capacity(4, rand::thread_rng());
+    ///
+    /// rw.push(1., 'r');
+    /// assert_eq!(rw.pop(), Some((1.0, 'r')));
+    /// assert_eq!(rw.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<(P, T)> {
+
+        if let Some(index) = self.get_random_index() {
+
+            let proba = self.weights.get(index);
+            self.weights.add(index, P::zero() - proba);
+            self.len -= 1;
+            self.free.push(index);
+            self.items[index].take().map(|data| (proba, data))
+        }
+        else { None }
+    }
+}
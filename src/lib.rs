@@ -10,12 +10,15 @@
 //!
 //! # Examples
 //!
-//! You can explicitly create a `RandomWheel<T>` with `new()`:
+//! You can explicitly create a `RandomWheel<T>` with `new()`, passing in the
+//! `Rng` it should draw from (e.g. `rand::thread_rng()`, or a seeded
+//! `StdRng` for reproducible runs):
 //!
 //! ```
 //! use random_wheel::RandomWheel;
+//! extern crate rand;
 //!
-//! let rw: RandomWheel<u8> = RandomWheel::new();
+//! let rw: RandomWheel<f64, char, _> = RandomWheel::new(rand::thread_rng());
 //! ```
 //!
 //! You can `push` values onto the random-wheel (which will grow the wheel as needed):
@@ -24,8 +27,9 @@
 //!
 //! ```
 //! use random_wheel::RandomWheel;
+//! extern crate rand;
 //!
-//! let mut rw = RandomWheel::new();
+//! let mut rw = RandomWheel::new(rand::thread_rng());
 //!
 //! rw.push(5., 'a');
 //! rw.push(1., 'b');
@@ -35,4 +39,9 @@
 //! ```
 
 mod random_wheel;
-pub use random_wheel::RandomWheel;
+mod alias_wheel;
+mod fenwick;
+mod indexed_wheel;
+pub use random_wheel::{RandomWheel, DrainWeighted};
+pub use alias_wheel::AliasTable;
+pub use indexed_wheel::IndexedRandomWheel;
@@ -1,6 +1,7 @@
 extern crate rand;
 extern crate num;
 
+use std::cmp;
 use std::fmt::Display;
 use std::iter::repeat;
 use std::collections::VecDeque;
@@ -8,25 +9,29 @@ use std::collections::vec_deque::{ Iter, IterMut };
 use self::rand::Rng;
 use self::rand::distributions::range::SampleRange;
 use self::num::{Float};
+use ::alias_wheel::AliasTable;
 
 /// a little implementation of a random-wheel.
-pub struct RandomWheel<P: SampleRange + Float, T> {
+pub struct RandomWheel<P: SampleRange + Float, T, R: Rng> {
     /// the sum of all probabilities in this wheel.
     proba_sum: P,
     /// all the (probability, data) in a linked-list to pop easily.
-    cards: VecDeque<(P, T)>
+    cards: VecDeque<(P, T)>,
+    /// the generator used to draw from this wheel.
+    rng: R
 }
 
-impl<P: SampleRange + Float, T: Clone> Clone for RandomWheel<P, T> {
-    fn clone(&self) -> RandomWheel<P, T> {
+impl<P: SampleRange + Float, T: Clone, R: Rng + Clone> Clone for RandomWheel<P, T, R> {
+    fn clone(&self) -> RandomWheel<P, T, R> {
         RandomWheel{
             proba_sum: self.proba_sum,
-            cards: self.cards.clone()
+            cards: self.cards.clone(),
+            rng: self.rng.clone()
         }
     }
 }
 
-impl<P: SampleRange + Float + Display, T> Iterator for RandomWheel<P, T> {
+impl<P: SampleRange + Float + Display, T, R: Rng> Iterator for RandomWheel<P, T, R> {
 
     type Item = (P, T);
 
@@ -35,57 +40,124 @@ impl<P: SampleRange + Float + Display, T> Iterator for RandomWheel<P, T> {
     }
 }
 
-impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
-    /// create a new random-wheel from vector.
+/// a draining iterator over a [`RandomWheel`], yielding elements in
+/// weighted-random order and leaving the wheel empty when exhausted.
+/// Created by [`RandomWheel::drain_weighted`].
+pub struct DrainWeighted<'a, P: 'a + SampleRange + Float + Display, T: 'a, R: 'a + Rng> {
+    wheel: &'a mut RandomWheel<P, T, R>
+}
+
+impl<'a, P: SampleRange + Float + Display, T, R: Rng> Iterator for DrainWeighted<'a, P, T, R> {
+
+    type Item = (P, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.wheel.pop()
+    }
+}
+
+impl<P: SampleRange + Float + Display, T, R: Rng> Extend<(P, T)> for RandomWheel<P, T, R> {
+    /// bulk-appends `(probability, data)` pairs, as if calling `push` for each one.
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::RandomWheel;
+    /// extern crate rand;
+    ///
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
+    /// rw.push(0.1, 5);
+    ///
+    /// rw.extend(vec![(0.2, 10), (0.3, 15)]);
+    /// assert_eq!(rw.len(), 3);
+    /// ```
+    fn extend<I: IntoIterator<Item = (P, T)>>(&mut self, iter: I) {
+        for (proba, data) in iter {
+            self.push(proba, data);
+        }
+    }
+}
+
+impl<P: SampleRange + Float + Display, T, R: Rng> RandomWheel<P, T, R> {
+    /// builds a wheel from an iterator of `(probability, data)` pairs,
+    /// drawing with `rng`. Equivalent to `RandomWheel::new(rng)` followed by
+    /// `extend`, but composes with iterator pipelines in one expression.
+    ///
+    /// There's no `FromIterator` impl (and hence no plain `.collect()`):
+    /// `RandomWheel` needs an `Rng` to draw with, and none of `rand`'s
+    /// generators implement `Default`, so `from_iter` would have nothing to
+    /// construct one from.
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::RandomWheel;
+    /// extern crate rand;
+    ///
+    /// let pairs = vec![(0.1, 10), (0.2, 15)];
+    /// let rw: RandomWheel<f64, i32, _> = RandomWheel::from_pairs(pairs, rand::thread_rng());
+    /// assert_eq!(rw.len(), 2);
+    /// ```
+    pub fn from_pairs<I: IntoIterator<Item = (P, T)>>(iter: I, rng: R) -> RandomWheel<P, T, R> {
+        let mut wheel = RandomWheel::new(rng);
+        wheel.extend(iter);
+        wheel
+    }
+
+    /// create a new random-wheel from vector, drawing with `rng`.
     /// # Example
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
     /// let numbers: Vec<_> = (0..20).collect();
     ///
     /// // default probability is set to 1.0 for each element
-    /// let rw: RandomWheel<u8> = RandomWheel::from_vec(numbers);
+    /// let rw: RandomWheel<f64, _, _> = RandomWheel::from_vec(numbers, rand::thread_rng());
     /// ```
-    pub fn from_vec(vector: Vec<T>) -> RandomWheel<P, T> {
+    pub fn from_vec(vector: Vec<T>, rng: R) -> RandomWheel<P, T, R> {
 
         RandomWheel {
 
             proba_sum: P::from(vector.len()).unwrap(),
-            cards: repeat(P::one()).into_iter().zip(vector).collect()
+            cards: repeat(P::one()).into_iter().zip(vector).collect(),
+            rng
         }
     }
 
-    /// create a new empty random-wheel.
+    /// create a new empty random-wheel, drawing with `rng`.
     /// # Example
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let rw: RandomWheel<u8> = RandomWheel::new();
+    /// let rw: RandomWheel<f64, u8, _> = RandomWheel::new(rand::thread_rng());
     /// ```
-    pub fn new() -> RandomWheel<P, T> {
+    pub fn new(rng: R) -> RandomWheel<P, T, R> {
         RandomWheel {
             proba_sum: P::zero(),
-            cards: VecDeque::new()
+            cards: VecDeque::new(),
+            rng
         }
     }
 
-    /// Creates an empty RandomWheel with space for at least n elements.
+    /// Creates an empty RandomWheel with space for at least n elements, drawing with `rng`.
     /// # Example
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
     /// let numbers: Vec<_> = (0..20).collect();
-    /// let mut rw: RandomWheel<u8> = RandomWheel::with_capacity(numbers.len());
+    /// let mut rw: RandomWheel<f64, u8, _> = RandomWheel::with_capacity(numbers.len(), rand::thread_rng());
     ///
     /// assert_eq!(rw.len(), 0);
     /// ```
-    pub fn with_capacity(n: usize) -> RandomWheel<P, T> {
+    pub fn with_capacity(n: usize, rng: R) -> RandomWheel<P, T, R> {
         RandomWheel {
             proba_sum: P::zero(),
-            cards: VecDeque::with_capacity(n)
+            cards: VecDeque::with_capacity(n),
+            rng
         }
     }
 
@@ -112,8 +184,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let rw: RandomWheel<u8> = RandomWheel::new();
+    /// let rw: RandomWheel<f64, u8, _> = RandomWheel::new(rand::thread_rng());
     ///
     /// println!("actual capacity: {}", rw.capacity());
     /// ```
@@ -126,8 +199,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// assert_eq!(rw.len(), 0);
     ///
@@ -146,8 +220,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// rw.push(1., 'r');
     /// rw.push(1., 'c');
@@ -168,8 +243,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// assert_eq!(rw.is_empty(), true);
     ///
@@ -188,8 +264,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// rw.push(1., 'r');
     /// rw.push(1., 'c');
@@ -211,8 +288,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// rw.push(1., 'r');
     /// rw.push(1., 'c');
@@ -233,8 +311,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// rw.push(1., 'r');
     /// rw.push(1., 'c');
@@ -273,8 +352,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// rw.push(1.5, 'r');
     /// rw.push(2., 'c');
@@ -286,18 +366,77 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
         self.proba_sum
     }
 
-    /// returns a random distance to browser between 0 and the probabilities sum.
-    fn gen_random_dist(&self) -> P {
+    /// builds a frozen [`AliasTable`](::AliasTable) from this wheel using
+    /// [Walker's alias method](https://en.wikipedia.org/wiki/Alias_method), so
+    /// each subsequent draw is O(1) instead of the O(n) scan `peek`/`pop` do.
+    ///
+    /// The table is a snapshot: rebuild it with `build_alias` after any
+    /// `push`/`pop` or probability edit on the wheel it came from.
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::RandomWheel;
+    /// extern crate rand;
+    ///
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
+    ///
+    /// rw.push(5., 'a');
+    /// rw.push(1., 'b');
+    ///
+    /// let mut table = rw.build_alias(rand::thread_rng());
+    /// assert_eq!(table.len(), 2);
+    /// ```
+    pub fn build_alias<R2: Rng>(&self, rng: R2) -> AliasTable<P, T, R2> where T: Clone {
+
+        let n = self.len();
+        let items: Vec<(P, T)> = self.cards.iter().cloned().collect();
+        let mut prob = vec![P::zero(); n];
+        let mut alias = vec![0usize; n];
+
+        if n > 0 {
+
+            let scale = P::from(n).unwrap() / self.proba_sum;
+            let mut scaled: Vec<P> = items.iter().map(|&(w, _)| w * scale).collect();
+
+            let mut small: Vec<usize> = Vec::new();
+            let mut large: Vec<usize> = Vec::new();
+            for (id, &w) in scaled.iter().enumerate() {
+                if w < P::one() { small.push(id); } else { large.push(id); }
+            }
+
+            while !small.is_empty() && !large.is_empty() {
+
+                let s = small.pop().unwrap();
+                let l = large.pop().unwrap();
+
+                prob[s] = scaled[s];
+                alias[s] = l;
+
+                scaled[l] = scaled[l] - (P::one() - scaled[s]);
+                if scaled[l] < P::one() { small.push(l); } else { large.push(l); }
+            }
+
+            // leftover indices were never paired; they're drawn unconditionally.
+            for id in large { prob[id] = P::one(); }
+            for id in small { prob[id] = P::one(); }
+        }
+
+        AliasTable::from_parts(items, prob, alias, rng)
+    }
+
+    /// returns a random distance to browser between 0 and the probabilities sum,
+    /// drawn from the wheel's own generator.
+    fn gen_random_dist(&mut self) -> P {
 
         match self.proba_sum {
 
-            sum if sum > P::zero() => rand::thread_rng().gen_range(P::zero(), sum),
+            sum if sum > P::zero() => self.rng.gen_range(P::zero(), sum),
             _               => P::zero()
         }
     }
 
     /// returns a random index in self.cards.
-    fn get_random_index(&self) -> Option<usize> {
+    fn get_random_index(&mut self) -> Option<usize> {
 
         if self.is_empty() {
             return None;
@@ -324,21 +463,122 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
         return None;
     }
 
+    /// returns a random distance to browse between 0 and the probabilities
+    /// sum, drawn from a caller-supplied generator instead of `self.rng`.
+    fn gen_random_dist_with<R2: Rng>(&self, rng: &mut R2) -> P {
+
+        match self.proba_sum {
+
+            sum if sum > P::zero() => rng.gen_range(P::zero(), sum),
+            _               => P::zero()
+        }
+    }
+
+    /// returns a random index in self.cards, drawn from a caller-supplied
+    /// generator instead of `self.rng`.
+    fn get_random_index_with<R2: Rng>(&self, rng: &mut R2) -> Option<usize> {
+
+        if self.is_empty() {
+            return None;
+        }
+
+        if self.len() <= 1 {
+            // NOTE: fast path
+            return Some(0);
+        }
+
+        let zero = P::zero();
+
+        let mut dist = self.gen_random_dist_with(rng);
+        for (id, &(ref proba, _)) in self.cards.iter().enumerate() {
+
+            dist = dist - *proba;
+            if dist <= zero {
+                return Some(id);
+            }
+        }
+
+        // NOTE: this is unreachable
+
+        return None;
+    }
+
+    /// like [`peek`](#method.peek), but draws with a caller-supplied `rng`
+    /// instead of the wheel's own, the way `rand`'s `SliceRandom::choose`
+    /// does for slices. Doesn't remove the element or touch `self.rng`.
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::RandomWheel;
+    /// extern crate rand;
+    ///
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
+    /// rw.push(1., 'r');
+    ///
+    /// let mut rng = rand::thread_rng();
+    /// assert_eq!(rw.choose(&mut rng), Some((1.0, &'r')));
+    /// ```
+    pub fn choose<R2: Rng>(&self, rng: &mut R2) -> Option<(P, &T)> {
+
+        if let Some(index) = self.get_random_index_with(rng) {
+
+            if let Some(&(proba, ref data)) = self.cards.get(index) {
+                Some((proba, data))
+            }
+            else { None }
+        }
+        else { None }
+    }
+
+    /// like [`peek_mut`](#method.peek_mut), but draws with a caller-supplied
+    /// `rng` instead of the wheel's own, the way `rand`'s
+    /// `SliceRandom::choose_mut` does for slices. Doesn't remove the element
+    /// or touch `self.rng`.
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::RandomWheel;
+    /// extern crate rand;
+    ///
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
+    /// rw.push(1., 'r');
+    ///
+    /// let mut rng = rand::thread_rng();
+    /// match rw.choose_mut(&mut rng) {
+    ///     Some((_, val)) => *val = 'b',
+    ///     None => {}
+    /// }
+    ///
+    /// assert_eq!(rw.peek(), Some((1.0, &'b')));
+    /// ```
+    pub fn choose_mut<R2: Rng>(&mut self, rng: &mut R2) -> Option<(P, &mut T)> {
+
+        if let Some(index) = self.get_random_index_with(rng) {
+
+            if let Some(&mut (proba, ref mut data)) = self.cards.get_mut(index) {
+                Some((proba, data))
+            }
+            else { None }
+        }
+        else { None }
+    }
+
     /// returns a ref to the randomly peeked element with
     /// it's probality to be peeked.
     /// # Example
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// rw.push(1., 'r');
     ///
     /// assert_eq!(rw.peek(), Some((1.0, &'r')));
     /// assert_eq!(rw.peek(), Some((1.0, &'r')));
     /// ```
-    pub fn peek(&self) -> Option<(P, &T)> {
+    pub fn peek(&mut self) -> Option<(P, &T)> {
 
         if let Some(index) = self.get_random_index() {
 
@@ -356,8 +596,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// rw.push(1., 'r');
     ///
@@ -386,8 +627,9 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
     ///
     /// ```
     /// use random_wheel::RandomWheel;
+    /// extern crate rand;
     ///
-    /// let mut rw = RandomWheel::new();
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
     ///
     /// rw.push(1., 'r');
     ///
@@ -410,4 +652,60 @@ impl<P: SampleRange + Float + Display, T> RandomWheel<P, T> {
         }
         else { None }
     }
+
+    /// pops up to `k` weighted-without-replacement elements, rescaling the
+    /// remaining distribution after each removal. Returns fewer than `k`
+    /// elements (possibly none) if the wheel holds less than `k`.
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::RandomWheel;
+    /// extern crate rand;
+    ///
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
+    ///
+    /// rw.push(1., 'r');
+    /// rw.push(1., 'c');
+    /// rw.push(1., 'a');
+    ///
+    /// assert_eq!(rw.sample(2).len(), 2);
+    /// assert_eq!(rw.len(), 1);
+    ///
+    /// // asking for more than the wheel holds just empties it
+    /// assert_eq!(rw.sample(10).len(), 1);
+    /// ```
+    pub fn sample(&mut self, k: usize) -> Vec<(P, T)> {
+
+        let k = cmp::min(k, self.len());
+        let mut picked = Vec::with_capacity(k);
+
+        for _ in 0..k {
+            match self.pop() {
+                Some(pair) => picked.push(pair),
+                None => break
+            }
+        }
+
+        picked
+    }
+
+    /// returns a draining iterator yielding elements in weighted-random
+    /// order, emptying the wheel as it's consumed.
+    /// # Example
+    ///
+    /// ```
+    /// use random_wheel::RandomWheel;
+    /// extern crate rand;
+    ///
+    /// let mut rw = RandomWheel::new(rand::thread_rng());
+    ///
+    /// rw.push(1., 'r');
+    /// rw.push(1., 'c');
+    ///
+    /// assert_eq!(rw.drain_weighted().count(), 2);
+    /// assert_eq!(rw.len(), 0);
+    /// ```
+    pub fn drain_weighted(&mut self) -> DrainWeighted<P, T, R> {
+        DrainWeighted { wheel: self }
+    }
 }
@@ -6,7 +6,7 @@ extern crate rand;
 #[cfg(test)]
 mod tests {
 
-    use random_wheel::RandomWheel;
+    use random_wheel::{RandomWheel, IndexedRandomWheel};
     use rand;
 
     // need nightly
@@ -76,6 +76,162 @@ mod tests {
         assert_eq!(wheel.proba_sum(), 11.5);
     }
 
+    #[test]
+    fn test_build_alias_samples_only_pushed_values() {
+
+        let mut wheel = RandomWheel::new(rand::thread_rng());
+
+        wheel.push(5., 'a');
+        wheel.push(1., 'b');
+
+        let mut table = wheel.build_alias(rand::thread_rng());
+        assert_eq!(table.len(), 2);
+
+        for _ in 0..50 {
+            let (proba, value) = table.sample().expect("table should not be empty");
+            assert!(proba == 5. || proba == 1.);
+            assert!(*value == 'a' || *value == 'b');
+        }
+    }
+
+    #[test]
+    fn test_build_alias_on_empty_wheel() {
+
+        let wheel: RandomWheel<f64, char, _> = RandomWheel::new(rand::thread_rng());
+
+        let mut table = wheel.build_alias(rand::thread_rng());
+        assert_eq!(table.len(), 0);
+        assert_eq!(table.sample(), None);
+    }
+
+    #[test]
+    fn test_indexed_random_wheel_pop_to_empty() {
+
+        let mut wheel = IndexedRandomWheel::with_capacity(4, rand::thread_rng());
+
+        wheel.push(1., 'r');
+        wheel.push(1., 'c');
+        wheel.push(1., 'a');
+        assert_eq!(wheel.len(), 3);
+
+        let mut popped = Vec::new();
+        while let Some((proba, value)) = wheel.pop() {
+            assert_eq!(proba, 1.);
+            popped.push(value);
+        }
+        popped.sort();
+
+        assert_eq!(popped, vec!['a', 'c', 'r']);
+        assert_eq!(wheel.len(), 0);
+        assert_eq!(wheel.pop(), None);
+    }
+
+    #[test]
+    fn test_indexed_random_wheel_grows_past_capacity() {
+
+        let mut wheel = IndexedRandomWheel::with_capacity(1, rand::thread_rng());
+
+        for _ in 0..10 {
+            wheel.push(1., 'x');
+        }
+
+        assert_eq!(wheel.len(), 10);
+        assert_eq!(wheel.proba_sum(), 10.);
+    }
+
+    #[test]
+    fn test_indexed_random_wheel_reuses_tombstoned_slots() {
+
+        let mut wheel = IndexedRandomWheel::with_capacity(4, rand::thread_rng());
+
+        wheel.push(1., 'a');
+        wheel.push(1., 'b');
+        assert_eq!(wheel.capacity(), 4);
+
+        // interleaving push/pop across many generations must not grow the
+        // wheel past its initial capacity, since each pop frees a slot push
+        // can reuse.
+        for _ in 0..100 {
+            wheel.pop();
+            wheel.push(1., 'x');
+        }
+
+        assert_eq!(wheel.len(), 2);
+        assert_eq!(wheel.capacity(), 4);
+    }
+
+    #[test]
+    fn test_indexed_random_wheel_set_weight() {
+
+        let mut wheel = IndexedRandomWheel::with_capacity(4, rand::thread_rng());
+
+        wheel.push(1., 'a');
+        wheel.push(1., 'b');
+        assert_eq!(wheel.proba_sum(), 2.);
+
+        wheel.set_weight(0, 4.);
+        assert_eq!(wheel.proba_sum(), 5.);
+    }
+
+    #[test]
+    fn test_sample_without_replacement() {
+
+        let mut wheel = RandomWheel::new(rand::thread_rng());
+
+        wheel.push(1., 'r');
+        wheel.push(1., 'c');
+        wheel.push(1., 'a');
+
+        let picked = wheel.sample(2);
+        assert_eq!(picked.len(), 2);
+        assert_eq!(wheel.len(), 1);
+
+        // asking for more than the wheel holds just drains what's left
+        let rest = wheel.sample(10);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(wheel.len(), 0);
+    }
+
+    #[test]
+    fn test_drain_weighted_empties_the_wheel() {
+
+        let mut wheel = RandomWheel::new(rand::thread_rng());
+
+        wheel.push(1., 'r');
+        wheel.push(1., 'c');
+        wheel.push(1., 'a');
+
+        assert_eq!(wheel.drain_weighted().count(), 3);
+        assert_eq!(wheel.len(), 0);
+    }
+
+    #[test]
+    fn test_choose_does_not_remove() {
+
+        let mut wheel = RandomWheel::new(rand::thread_rng());
+        wheel.push(1., 'r');
+
+        let mut rng = rand::thread_rng();
+        assert_eq!(wheel.choose(&mut rng), Some((1., &'r')));
+        assert_eq!(wheel.len(), 1);
+    }
+
+    #[test]
+    fn test_choose_mut_modifies_in_place() {
+
+        let mut wheel = RandomWheel::new(rand::thread_rng());
+        wheel.push(1., 'r');
+
+        let mut rng = rand::thread_rng();
+        match wheel.choose_mut(&mut rng) {
+            Some((_, val)) => *val = 'b',
+            None => {}
+        }
+
+        assert_eq!(wheel.len(), 1);
+        assert_eq!(wheel.peek(), Some((1., &'b')));
+    }
+
     /*
     // no clone struct
     #[derive(PartialEq, Eq, Debug)] // for assert!